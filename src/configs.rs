@@ -0,0 +1,147 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecurityProtocol {
+    Plaintext,
+    SaslPlaintext,
+    Ssl,
+    SaslSsl,
+}
+
+impl fmt::Display for SecurityProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            SecurityProtocol::Plaintext => "PLAINTEXT",
+            SecurityProtocol::SaslPlaintext => "SASL_PLAINTEXT",
+            SecurityProtocol::Ssl => "SSL",
+            SecurityProtocol::SaslSsl => "SASL_SSL",
+        };
+        f.write_str(s)
+    }
+}
+
+/// SASL mechanism, mirroring the subset `librdkafka` supports out of the
+/// box. `Gssapi` uses the `kerberos_*` fields of [`KafkaSaslConfig`]:
+/// `kerberos_service_name` defaults to `kafka` when unset, and
+/// `kerberos_keytab`/`kerberos_principal` are passed on only when set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaslMechanism {
+    Plain,
+    ScramSha256,
+    ScramSha512,
+    Gssapi,
+}
+
+impl fmt::Display for SaslMechanism {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            SaslMechanism::Plain => "PLAIN",
+            SaslMechanism::ScramSha256 => "SCRAM-SHA-256",
+            SaslMechanism::ScramSha512 => "SCRAM-SHA-512",
+            SaslMechanism::Gssapi => "GSSAPI",
+        };
+        f.write_str(s)
+    }
+}
+
+/// SASL credentials for `SecurityProtocol::SaslPlaintext` /
+/// `SecurityProtocol::SaslSsl`. `username`/`password` are used for
+/// `Plain`/`ScramSha*`; `kerberos_*` are used for `Gssapi`.
+#[derive(Debug, Clone, Default)]
+pub struct KafkaSaslConfig {
+    pub mechanism: Option<SaslMechanism>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub kerberos_service_name: Option<String>,
+    pub kerberos_keytab: Option<String>,
+    pub kerberos_principal: Option<String>,
+}
+
+/// TLS material for `SecurityProtocol::Ssl` / `SaslSsl`. Certs/keys can be
+/// supplied as file paths (`cafile`/`clientfile`/`clientkeyfile`) or as
+/// inline PEM strings (`ca_pem`/`certificate_pem`/`key_pem`) for deployments
+/// that pull certs from a secret store rather than the filesystem; the PEM
+/// fields take precedence when both are set.
+#[derive(Debug, Clone)]
+pub struct KafkaTlsConfig {
+    pub cafile: String,
+    pub clientfile: String,
+    pub clientkeyfile: String,
+    pub ca_pem: Option<String>,
+    pub certificate_pem: Option<String>,
+    pub key_pem: Option<String>,
+    /// Disables broker hostname verification when `false`.
+    pub verify_hostname: bool,
+}
+
+impl Default for KafkaTlsConfig {
+    fn default() -> Self {
+        KafkaTlsConfig {
+            cafile: String::new(),
+            clientfile: String::new(),
+            clientkeyfile: String::new(),
+            ca_pem: None,
+            certificate_pem: None,
+            key_pem: None,
+            verify_hostname: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct KafkaAuthConfig {
+    pub brokers: Vec<String>,
+    pub security_protocol: SecurityProtocol,
+    pub tls: Option<KafkaTlsConfig>,
+    pub sasl: Option<KafkaSaslConfig>,
+}
+
+impl KafkaAuthConfig {
+    pub fn get_security_protocol(&self) -> SecurityProtocol {
+        self.security_protocol
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct KafkaConsumerConfig {
+    pub auth: KafkaAuthConfig,
+    pub group_id: String,
+    pub topic: String,
+    pub partition: Option<i32>,
+    pub mode: ConsumerMode,
+}
+
+/// Selects how a consumer coordinates with other consumers on the same
+/// topic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsumerMode {
+    /// No group coordination: assigns partitions directly under an
+    /// ephemeral group id and starts at the tail. Good for tailing a
+    /// topic without affecting any real consumer group's progress.
+    RealTime,
+    /// Joins `group_id` and commits/reads stored offsets, so a restart
+    /// resumes where it left off.
+    Resumable,
+    /// Subscribes to the topic so several kafcat instances sharing
+    /// `group_id` split the topic's partitions via the group coordinator.
+    LoadBalanced,
+}
+
+#[derive(Debug, Clone)]
+pub struct KafkaProducerConfig {
+    pub auth: KafkaAuthConfig,
+    pub topic: String,
+    /// Pins every produced message to this partition, bypassing the
+    /// default partitioner. Used for partition-preserving mirroring.
+    pub partition: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum KafkaOffset {
+    Beginning,
+    End,
+    Stored,
+    Offset(i64),
+    OffsetInterval(i64, i64),
+    TimeInterval(i64, i64),
+}
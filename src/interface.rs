@@ -0,0 +1,41 @@
+use crate::configs::{KafkaConsumerConfig, KafkaOffset, KafkaProducerConfig};
+use crate::message::KafkaMessage;
+use crate::Result;
+use async_trait::async_trait;
+
+/// Ties a backend's concrete consumer/producer types together so the rest
+/// of the crate can be generic over `KafkaInterface::Consumer` /
+/// `KafkaInterface::Producer` instead of naming `rdkafka` directly.
+pub trait KafkaInterface {
+    type Consumer: KafkaConsumer;
+    type Producer: KafkaProducer;
+}
+
+#[async_trait]
+pub trait KafkaConsumer {
+    async fn from_config(config: KafkaConsumerConfig) -> Self
+    where
+        Self: Sized;
+
+    async fn set_offset_and_subscribe(&self, offset: KafkaOffset) -> Result<()>;
+
+    async fn get_offset(&self) -> Result<i64>;
+
+    async fn get_watermarks(&self) -> Result<(i64, i64)>;
+
+    async fn recv(&self) -> Result<KafkaMessage>;
+
+    /// Stores and commits `msg`'s offset (plus one, i.e. the next offset to
+    /// read) for the configured topic/partition, so a subsequent `assign`
+    /// with `KafkaOffset::Stored` resumes from here.
+    async fn commit(&self, msg: &KafkaMessage) -> Result<()>;
+}
+
+#[async_trait]
+pub trait KafkaProducer {
+    async fn from_config(config: KafkaProducerConfig) -> Self
+    where
+        Self: Sized;
+
+    async fn write_one(&self, msg: KafkaMessage) -> Result<()>;
+}
@@ -0,0 +1,14 @@
+#[macro_use]
+extern crate log;
+
+mod configs;
+mod interface;
+mod message;
+mod rdkafka_impl;
+
+pub use configs::*;
+pub use interface::*;
+pub use message::*;
+pub use rdkafka_impl::*;
+
+pub type Result<T> = anyhow::Result<T>;
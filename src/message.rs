@@ -0,0 +1,11 @@
+/// A single Kafka record, normalized across backends so the CLI pipeline
+/// (read from one interface, write to another) doesn't need to know which
+/// concrete client produced or will consume it.
+#[derive(Debug, Clone, Default)]
+pub struct KafkaMessage {
+    pub key: Vec<u8>,
+    pub payload: Vec<u8>,
+    pub timestamp: i64,
+    pub headers: Vec<(String, Option<Vec<u8>>)>,
+    pub offset: i64,
+}
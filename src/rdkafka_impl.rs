@@ -1,26 +1,43 @@
 use crate::configs::KafkaProducerConfig;
 use crate::configs::{KafkaAuthConfig, KafkaConsumerConfig};
-use crate::configs::{KafkaOffset, SecurityProtocol};
+use crate::configs::{
+    ConsumerMode, KafkaOffset, KafkaSaslConfig, KafkaTlsConfig, SaslMechanism, SecurityProtocol,
+};
 use crate::interface::KafkaConsumer;
 use crate::interface::KafkaInterface;
 use crate::interface::KafkaProducer;
 use crate::message::KafkaMessage;
 use crate::Result;
-use rdkafka::admin::{AdminClient, AdminOptions, NewTopic, TopicReplication};
+use rdkafka::admin::{
+    AdminClient, AdminOptions, AlterConfig, NewPartitions, NewTopic, ResourceSpecifier,
+    TopicReplication, TopicResult,
+};
 use rdkafka::client::DefaultClientContext;
 use rdkafka::config::RDKafkaLogLevel;
 use rdkafka::consumer::Consumer;
-use rdkafka::consumer::StreamConsumer;
+use rdkafka::consumer::{CommitMode, StreamConsumer};
 use rdkafka::error::KafkaResult;
+use rdkafka::message::{Header, Headers, OwnedHeaders};
+use rdkafka::metadata::Metadata;
 use rdkafka::producer::FutureProducer;
 use rdkafka::producer::FutureRecord;
 use rdkafka::ClientConfig;
 use rdkafka::Message;
 use rdkafka::Offset;
 use rdkafka::TopicPartitionList;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::task::block_in_place;
 
+/// A group id that no other consumer will ever share, used by
+/// `ConsumerMode::RealTime` so it never collides with a real consumer
+/// group's committed offsets.
+fn ephemeral_group_id() -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    format!("kafcat-ephemeral-{}-{}", std::process::id(), now.as_nanos())
+}
+
 pub struct RdKafka {}
 impl KafkaInterface for RdKafka {
     type Consumer = RdkafkaConsumer;
@@ -37,7 +54,7 @@ impl RdkafkaConsumer {
     }
 }
 
-fn config_client(auth: &KafkaAuthConfig) -> ClientConfig {
+fn config_client(auth: &KafkaAuthConfig) -> Result<ClientConfig> {
     let mut config = ClientConfig::new();
     config
         .set(
@@ -48,19 +65,80 @@ fn config_client(auth: &KafkaAuthConfig) -> ClientConfig {
     match auth.get_security_protocol() {
         SecurityProtocol::Plaintext => {}
         SecurityProtocol::SaslPlaintext => {
-            unimplemented!("SASL plaintext not implemented")
+            set_sasl_config(&mut config, auth.sasl.as_ref().unwrap())?;
         }
         SecurityProtocol::Ssl => {
-            let tls = auth.tls.as_ref().unwrap();
+            set_tls_config(&mut config, auth.tls.as_ref().unwrap());
+        }
+        SecurityProtocol::SaslSsl => {
+            set_tls_config(&mut config, auth.tls.as_ref().unwrap());
+            set_sasl_config(&mut config, auth.sasl.as_ref().unwrap())?;
+        }
+    }
+    Ok(config)
+}
+
+/// Sets the `ssl.*` rdkafka keys from `tls`. PEM strings take precedence
+/// over file paths when both are set, since they're mutually exclusive in
+/// practice (one deployment style or the other).
+fn set_tls_config(config: &mut ClientConfig, tls: &KafkaTlsConfig) {
+    match &tls.ca_pem {
+        Some(pem) => {
+            config.set("ssl.ca.pem", pem);
+        }
+        None => {
             config.set("ssl.ca.location", &tls.cafile);
+        }
+    }
+    match &tls.certificate_pem {
+        Some(pem) => {
+            config.set("ssl.certificate.pem", pem);
+        }
+        None => {
             config.set("ssl.certificate.location", &tls.clientfile);
+        }
+    }
+    match &tls.key_pem {
+        Some(pem) => {
+            config.set("ssl.key.pem", pem);
+        }
+        None => {
             config.set("ssl.key.location", &tls.clientkeyfile);
         }
-        SecurityProtocol::SaslSsl => {
-            unimplemented!("SASL SSL not implemented")
+    }
+    config.set(
+        "ssl.endpoint.identification.algorithm",
+        if tls.verify_hostname { "https" } else { "none" },
+    );
+}
+
+/// Sets the `sasl.*` rdkafka keys for the mechanism configured in `sasl`.
+/// `Gssapi` authenticates via a Kerberos service name/keytab/principal
+/// instead of a username/password pair.
+fn set_sasl_config(config: &mut ClientConfig, sasl: &KafkaSaslConfig) -> Result<()> {
+    let mechanism = sasl
+        .mechanism
+        .ok_or_else(|| anyhow::anyhow!("SASL security protocol requires a sasl.mechanism"))?;
+    config.set("sasl.mechanisms", mechanism.to_string());
+    match mechanism {
+        SaslMechanism::Gssapi => {
+            config.set(
+                "sasl.kerberos.service.name",
+                sasl.kerberos_service_name.as_deref().unwrap_or("kafka"),
+            );
+            if let Some(keytab) = &sasl.kerberos_keytab {
+                config.set("sasl.kerberos.keytab", keytab);
+            }
+            if let Some(principal) = &sasl.kerberos_principal {
+                config.set("sasl.kerberos.principal", principal);
+            }
+        }
+        SaslMechanism::Plain | SaslMechanism::ScramSha256 | SaslMechanism::ScramSha512 => {
+            config.set("sasl.username", sasl.username.as_deref().unwrap_or(""));
+            config.set("sasl.password", sasl.password.as_deref().unwrap_or(""));
         }
     }
-    config
+    Ok(())
 }
 #[async_trait]
 impl KafkaConsumer for RdkafkaConsumer {
@@ -68,12 +146,22 @@ impl KafkaConsumer for RdkafkaConsumer {
     where
         Self: Sized,
     {
-        // TODO enable SSL and SASL
+        let group_id = match config.mode {
+            ConsumerMode::RealTime => ephemeral_group_id(),
+            ConsumerMode::Resumable | ConsumerMode::LoadBalanced => config.group_id.clone(),
+        };
+        let auto_offset_reset = match config.mode {
+            ConsumerMode::RealTime => "latest",
+            ConsumerMode::Resumable | ConsumerMode::LoadBalanced => "earliest",
+        };
+
         let stream: StreamConsumer = config_client(&config.auth)
-            .set("group.id", &config.group_id)
+            .expect("invalid kafka auth config")
+            .set("group.id", &group_id)
             .set("enable.partition.eof", "false")
             .set("session.timeout.ms", "6000")
             .set("enable.auto.commit", "false")
+            .set("auto.offset.reset", auto_offset_reset)
             .set_log_level(RDKafkaLogLevel::Debug)
             .create()
             .expect("Consumer creation failed");
@@ -83,6 +171,12 @@ impl KafkaConsumer for RdkafkaConsumer {
 
     async fn set_offset_and_subscribe(&self, offset: KafkaOffset) -> Result<()> {
         info!("set offset {:?}", offset);
+
+        if self.config.mode == ConsumerMode::LoadBalanced {
+            self.stream.subscribe(&[&self.config.topic])?;
+            return Ok(());
+        }
+
         let mut tpl = TopicPartitionList::new();
         let partition = self.config.partition.unwrap_or(0);
         let topic = self.config.topic.clone();
@@ -112,7 +206,15 @@ impl KafkaConsumer for RdkafkaConsumer {
     }
 
     async fn get_offset(&self) -> Result<i64> {
-        unimplemented!()
+        let partition = self.config.partition.unwrap_or(0);
+        let tpl = self.stream.position()?;
+        let offset = tpl
+            .find_partition(&self.config.topic, partition)
+            .ok_or_else(|| anyhow::anyhow!("no position for {}:{}", self.config.topic, partition))?
+            .offset()
+            .to_raw()
+            .ok_or_else(|| anyhow::anyhow!("no committed offset for {}:{}", self.config.topic, partition))?;
+        Ok(offset)
     }
 
     async fn get_watermarks(&self) -> Result<(i64, i64)> {
@@ -134,16 +236,37 @@ impl KafkaConsumer for RdkafkaConsumer {
         match locker.recv().await {
             Ok(x) => {
                 let msg = x.detach();
+                let headers = msg
+                    .headers()
+                    .map(|headers| {
+                        (0..headers.count())
+                            .map(|i| {
+                                let header = headers.get(i);
+                                (header.key.to_owned(), header.value.map(Vec::from))
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
                 Ok(KafkaMessage {
                     key: msg.key().map(Vec::from).unwrap_or_default(),
                     payload: msg.payload().map(Vec::from).unwrap_or_default(),
                     timestamp: msg.timestamp().to_millis().unwrap(),
-                    ..KafkaMessage::default() // TODO headers
+                    headers,
+                    offset: msg.offset(),
                 })
             }
             Err(err) => Err(anyhow::Error::from(err).into()),
         }
     }
+
+    async fn commit(&self, msg: &KafkaMessage) -> Result<()> {
+        let partition = self.config.partition.unwrap_or(0);
+        let mut tpl = TopicPartitionList::new();
+        tpl.add_partition_offset(&self.config.topic, partition, Offset::Offset(msg.offset + 1))
+            .unwrap();
+        self.stream.commit(&tpl, CommitMode::Async)?;
+        Ok(())
+    }
 }
 pub struct RdkafkaProducer {
     producer: FutureProducer,
@@ -156,8 +279,8 @@ impl KafkaProducer for RdkafkaProducer {
     where
         Self: Sized,
     {
-        // TODO enable SSL and SASL
         let producer = config_client(&config.auth)
+            .expect("invalid kafka auth config")
             .set("bootstrap.servers", &config.auth.brokers.join(" "))
             .set("message.timeout.ms", "5000")
             .create()
@@ -175,6 +298,22 @@ impl KafkaProducer for RdkafkaProducer {
         if !payload.is_empty() {
             record = record.payload(&payload)
         }
+        if !msg.headers.is_empty() {
+            let mut headers = OwnedHeaders::new();
+            for (key, value) in &msg.headers {
+                headers = headers.insert(Header {
+                    key,
+                    value: value.as_deref(),
+                });
+            }
+            record = record.headers(headers);
+        }
+        if msg.timestamp > 0 {
+            record = record.timestamp(msg.timestamp);
+        }
+        if let Some(partition) = self.config.partition {
+            record = record.partition(partition);
+        }
         self.producer
             .send(record, Duration::from_secs(0))
             .await
@@ -183,7 +322,9 @@ impl KafkaProducer for RdkafkaProducer {
     }
 }
 
-/// The admin client to kafka.
+/// The admin client to kafka: topic lifecycle, partition growth, and
+/// config management. Per-topic failures are returned as `Err` rather than
+/// panicking, so a caller managing several topics can report each one.
 pub struct RdKafkaAdmin {
     admin_client: AdminClient<DefaultClientContext>,
 }
@@ -191,6 +332,7 @@ pub struct RdKafkaAdmin {
 impl RdKafkaAdmin {
     pub fn create(auth: &KafkaAuthConfig) -> Self {
         let admin_client = config_client(auth)
+            .expect("invalid kafka auth config")
             .set("message.timeout.ms", "5000")
             .create()
             .expect("AdminClient creation error");
@@ -198,16 +340,89 @@ impl RdKafkaAdmin {
         Self { admin_client }
     }
 
-    pub async fn create_topic(&self, name: &str, num_partitions: i32) {
-        let topics = vec![NewTopic {
+    pub async fn create_topic(
+        &self,
+        name: &str,
+        num_partitions: i32,
+        replication_factor: i32,
+        configs: &[(String, String)],
+    ) -> Result<()> {
+        let mut topic = NewTopic::new(
             name,
             num_partitions,
-            replication: TopicReplication::Fixed(1),
-            config: vec![],
-        }];
-        self.admin_client
-            .create_topics(topics.iter(), &AdminOptions::default())
-            .await
-            .unwrap_or_else(|e| panic!("Faield to create topic {}: {}", name, e));
+            TopicReplication::Fixed(replication_factor),
+        );
+        for (key, value) in configs {
+            topic = topic.set(key, value);
+        }
+        let results = self
+            .admin_client
+            .create_topics(&[topic], &AdminOptions::default())
+            .await?;
+        topic_result(name, results)
+    }
+
+    pub async fn delete_topic(&self, name: &str) -> Result<()> {
+        let results = self
+            .admin_client
+            .delete_topics(&[name], &AdminOptions::default())
+            .await?;
+        topic_result(name, results)
     }
+
+    /// Fetches topic/partition metadata via the admin client's underlying
+    /// connection: pass `None` to list every topic, or `Some(name)` to
+    /// describe a single topic.
+    pub async fn describe_topics(&self, name: Option<&str>) -> Result<Metadata> {
+        let admin_client = &self.admin_client;
+        let name = name.map(str::to_owned);
+        let metadata = block_in_place(|| {
+            admin_client
+                .inner()
+                .fetch_metadata(name.as_deref(), Duration::from_secs(5))
+        })?;
+        Ok(metadata)
+    }
+
+    /// Grows `name` to `total_partition_count` partitions. Note this is the
+    /// desired *total*, not a number to add on top of the current count
+    /// (`rdkafka::admin::NewPartitions` takes the total as well) — passing a
+    /// value at or below the topic's current partition count fails.
+    pub async fn set_partition_count(&self, name: &str, total_partition_count: usize) -> Result<()> {
+        let partitions = NewPartitions::new(name, total_partition_count);
+        let results = self
+            .admin_client
+            .create_partitions(&[partitions], &AdminOptions::default())
+            .await?;
+        topic_result(name, results)
+    }
+
+    pub async fn alter_config(&self, name: &str, configs: &[(String, String)]) -> Result<()> {
+        let mut resource = AlterConfig::new(ResourceSpecifier::Topic(name));
+        for (key, value) in configs {
+            resource = resource.set(key, value);
+        }
+        let results = self
+            .admin_client
+            .alter_configs(&[resource], &AdminOptions::default())
+            .await?;
+        results
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("no alter_config result for {}", name))?
+            .map(|_| ())
+            .map_err(|(resource, code)| {
+                anyhow::anyhow!("alter_config {} failed: {:?} ({:?})", name, resource, code)
+            })
+    }
+}
+
+/// Unwraps the single-topic `TopicResult` rdkafka's admin calls return into
+/// a plain `Result<()>`, since every call site here operates on one topic.
+fn topic_result(name: &str, mut results: Vec<TopicResult>) -> Result<()> {
+    results
+        .pop()
+        .ok_or_else(|| anyhow::anyhow!("no admin result for {}", name))?
+        .map(|_| ())
+        .map_err(|(msg, code)| anyhow::anyhow!("{} failed: {} ({:?})", name, msg, code))
 }